@@ -10,6 +10,137 @@ use std::io::{BufRead, BufReader};
 enum Message {
     Status(String),
     Progress(f32),
+    Info(MediaInfo),
+}
+
+// Informationen zu einer einzelnen Spur (Video/Audio/Untertitel)
+#[derive(Clone, Default)]
+struct TrackInfo {
+    kind: String,        // "video", "audio" oder "subtitle"
+    codec: String,
+    width: String,       // Rohwert aus ffprobe, leer bei Audio
+    height: String,      // Rohwert aus ffprobe, leer bei Audio
+    resolution: String,  // z.B. "1920x1080", leer bei Audio
+    frame_rate: String,  // z.B. "29.97", leer bei Audio
+    sample_rate: String, // z.B. "48000", leer bei Video
+    language: String,
+}
+
+// Container- und Spur-Informationen der Eingabedatei
+#[derive(Clone, Default)]
+struct MediaInfo {
+    duration: String, // Gesamtdauer als "HH:MM:SS"
+    format: String,   // Major Brand bzw. Containerformat
+    tracks: Vec<TrackInfo>,
+}
+
+// Ein einzelner ffmpeg-Filter, z.B. `scale=1920:1080`
+struct Filter {
+    name: String,
+    args: String,
+}
+
+impl Filter {
+    fn scale(width: &str, height: &str) -> Self {
+        Self { name: "scale".to_string(), args: format!("{}:{}", width, height) }
+    }
+
+    fn fps(rate: &str) -> Self {
+        Self { name: "fps".to_string(), args: rate.to_string() }
+    }
+}
+
+// Eine Kette von Filtern, die zu einem `-vf`-Argument zusammengesetzt wird.
+#[derive(Default)]
+struct FilterGraph {
+    filters: Vec<Filter>,
+}
+
+impl FilterGraph {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn add(&mut self, filter: Filter) {
+        self.filters.push(filter);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    // Setzt die Filter durch Kommata getrennt zur ffmpeg-Filterkette zusammen.
+    fn to_arg(&self) -> String {
+        self.filters
+            .iter()
+            .map(|f| format!("{}={}", f.name, f.args))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+// Bildet die UI-Auswahl auf die konkreten ffmpeg-Flags ab.
+#[derive(Clone)]
+struct EncodeSettings {
+    width: String,       // Zielbreite, leer = unverändert
+    height: String,      // Zielhöhe, leer = unverändert
+    frame_rate: String,  // Zielbildrate, leer = unverändert
+    video_codec: String, // -c:v, leer = Standard
+    audio_codec: String, // -c:a, leer = Standard
+    bitrate: String,     // -b:v, z.B. "2000k"
+    crf: String,         // -crf, z.B. "23"
+}
+
+impl Default for EncodeSettings {
+    fn default() -> Self {
+        Self {
+            width: String::new(),
+            height: String::new(),
+            frame_rate: String::new(),
+            video_codec: String::new(),
+            audio_codec: String::new(),
+            bitrate: String::new(),
+            crf: String::new(),
+        }
+    }
+}
+
+impl EncodeSettings {
+    // Baut die Filterkette aus Auflösung und Bildrate auf.
+    fn filter_graph(&self) -> FilterGraph {
+        let mut graph = FilterGraph::new();
+        if !self.width.is_empty() || !self.height.is_empty() {
+            // Ein leeres Maß wird durch -1 ersetzt, damit ffmpeg das
+            // Seitenverhältnis beibehält.
+            let width = if self.width.is_empty() { "-1" } else { self.width.as_str() };
+            let height = if self.height.is_empty() { "-1" } else { self.height.as_str() };
+            graph.add(Filter::scale(width, height));
+        }
+        if !self.frame_rate.is_empty() {
+            graph.add(Filter::fps(&self.frame_rate));
+        }
+        graph
+    }
+
+    // Hängt Filter-, Codec- und Bitraten-Argumente an den ffmpeg-Aufruf an.
+    fn apply(&self, command: &mut Command) {
+        let graph = self.filter_graph();
+        if !graph.is_empty() {
+            command.arg("-vf").arg(graph.to_arg());
+        }
+        if !self.video_codec.is_empty() {
+            command.arg("-c:v").arg(&self.video_codec);
+        }
+        if !self.audio_codec.is_empty() {
+            command.arg("-c:a").arg(&self.audio_codec);
+        }
+        if !self.bitrate.is_empty() {
+            command.arg("-b:v").arg(&self.bitrate);
+        }
+        if !self.crf.is_empty() {
+            command.arg("-crf").arg(&self.crf);
+        }
+    }
 }
 
 // Liste der unterstützten Ausgabeformate: (Anzeigename, Dateiendung)
@@ -22,6 +153,132 @@ const SUPPORTED_FORMATS: &[(&str, &str)] = &[
     ("FLV", ".flv"),
 ];
 
+// Ausgabemodus: eine einzelne Datei oder segmentierte Streaming-Ausgabe.
+#[derive(Clone, Copy, PartialEq)]
+enum OutputMode {
+    SingleFile,
+    FragmentedMp4,
+    Dash,
+    Hls,
+}
+
+// Liste der Ausgabemodi: (Anzeigename, Modus)
+const OUTPUT_MODES: &[(&str, OutputMode)] = &[
+    ("Single File", OutputMode::SingleFile),
+    ("Fragmented MP4", OutputMode::FragmentedMp4),
+    ("DASH", OutputMode::Dash),
+    ("HLS", OutputMode::Hls),
+];
+
+impl OutputMode {
+    // Streaming-Modi schreiben mehrere Segmente in ein Verzeichnis.
+    fn needs_directory(&self) -> bool {
+        !matches!(self, OutputMode::SingleFile)
+    }
+
+    // Hängt die Ausgabeargumente an und liefert den Pfad der erzeugten
+    // Datei bzw. des Manifests zurück.
+    fn apply(&self, command: &mut Command, output_file: &str, output_dir: &str) -> String {
+        let manifest = |name: &str| Path::new(output_dir).join(name).to_string_lossy().to_string();
+        match self {
+            OutputMode::SingleFile => {
+                command.arg(output_file);
+                output_file.to_string()
+            }
+            OutputMode::FragmentedMp4 => {
+                let path = manifest("stream.mp4");
+                command.arg("-movflags").arg("frag_keyframe+empty_moov").arg(&path);
+                path
+            }
+            OutputMode::Dash => {
+                let path = manifest("manifest.mpd");
+                command.arg("-f").arg("dash").arg(&path);
+                path
+            }
+            OutputMode::Hls => {
+                let path = manifest("playlist.m3u8");
+                command.arg("-f").arg("hls").arg(&path);
+                path
+            }
+        }
+    }
+}
+
+// Zuschnitt-Einstellungen zum Extrahieren eines Segments.
+#[derive(Clone, Default)]
+struct TrimSettings {
+    start: String,    // Startzeit "HH:MM:SS", leer = vom Anfang
+    duration: String, // Dauer "HH:MM:SS", leer = bis zum Ende
+    accurate: bool,   // true = -ss nach -i (genau), false = vor -i (schnell)
+}
+
+impl TrimSettings {
+    // Argumente, die vor `-i` stehen müssen (schnelles Suchen).
+    fn apply_pre_input(&self, command: &mut Command) {
+        if !self.accurate && !self.start.is_empty() {
+            command.arg("-ss").arg(&self.start);
+        }
+    }
+
+    // Argumente, die nach `-i` als Ausgabeoptionen folgen (genaues Suchen, Dauer).
+    fn apply_post_input(&self, command: &mut Command) {
+        if self.accurate && !self.start.is_empty() {
+            command.arg("-ss").arg(&self.start);
+        }
+        if !self.duration.is_empty() {
+            command.arg("-t").arg(&self.duration);
+        }
+    }
+
+    // Effektive Dauer für die Fortschrittsberechnung: die zugeschnittene Dauer,
+    // andernfalls die Restdauer ab dem Startpunkt, sonst die Gesamtdauer.
+    fn effective_duration(&self, total: f32) -> f32 {
+        if let Ok(duration) = parse_duration(&self.duration) {
+            duration
+        } else if let Ok(start) = parse_duration(&self.start) {
+            (total - start).max(0.0)
+        } else {
+            total
+        }
+    }
+}
+
+// Einstellungen zum Einbetten einer externen Untertiteldatei.
+#[derive(Clone, Default)]
+struct SubtitleSettings {
+    path: String,       // externe .srt/.ass-Datei, leer = keine Untertitel
+    auto_sync: bool,    // Timing automatisch an die Sprache angleichen
+    split_sync: bool,   // Aufteilung in Blöcke mit eigenem Offset zulassen
+}
+
+// Ein einzelner Untertitel-Eintrag mit Zeitintervall.
+#[derive(Clone)]
+struct Cue {
+    start: f32, // Startzeit in Sekunden
+    end: f32,   // Endzeit in Sekunden
+    text: String,
+}
+
+// Ein einzelner Konvertierungsauftrag in der Warteschlange.
+#[derive(Clone)]
+struct Job {
+    input: String,
+    output: String,
+}
+
+// Gemeinsame Konvertierungsparameter für alle Jobs der Warteschlange.
+#[derive(Clone)]
+struct ConversionParams {
+    encode: EncodeSettings,
+    metadata_title: String,
+    metadata_artist: String,
+    metadata_description: String,
+    output_mode: OutputMode,
+    output_dir: String,
+    trim: TrimSettings,
+    subtitle: SubtitleSettings,
+}
+
 struct VideoConverterApp {
     input_file: String,
     output_file: String,
@@ -33,6 +290,14 @@ struct VideoConverterApp {
     metadata_title: String,
     metadata_artist: String,
     metadata_description: String,
+    media_info: Option<MediaInfo>, // Ergebnis der Medienanalyse der Eingabedatei
+    encode: EncodeSettings,        // Zielparameter für die Transkodierung
+    selected_mode: usize,          // Index des ausgewählten Ausgabemodus
+    output_dir: String,            // Zielverzeichnis für Streaming-Ausgabe
+    jobs: Vec<Job>,                // Warteschlange der Konvertierungsaufträge
+    concat: bool,                  // Eingaben zu einer Ausgabe verketten
+    trim: TrimSettings,            // Zuschnitt-Einstellungen
+    subtitle: SubtitleSettings,    // Untertitel-Einstellungen
 }
 
 impl Default for VideoConverterApp {
@@ -49,6 +314,14 @@ impl Default for VideoConverterApp {
             metadata_title: String::new(),
             metadata_artist: String::new(),
             metadata_description: String::new(),
+            media_info: None,
+            encode: EncodeSettings::default(),
+            selected_mode: 0,
+            output_dir: String::new(),
+            jobs: Vec::new(),
+            concat: false,
+            trim: TrimSettings::default(),
+            subtitle: SubtitleSettings::default(),
         }
     }
 }
@@ -64,6 +337,9 @@ impl App for VideoConverterApp {
                 Message::Progress(progress) => {
                     self.progress = progress;
                 }
+                Message::Info(info) => {
+                    self.media_info = Some(info);
+                }
             }
             ctx.request_repaint(); // GUI aktualisieren
         }
@@ -78,6 +354,19 @@ impl App for VideoConverterApp {
                 if ui.button("Browse").clicked() {
                     if let Some(path) = FileDialog::new().pick_file() {
                         self.input_file = path.to_string_lossy().to_string();
+                        // Sobald eine Datei gewählt wurde, die Medieninformationen ermitteln
+                        self.media_info = None;
+                        let input = self.input_file.clone();
+                        let tx = self.tx.clone();
+                        thread::spawn(move || {
+                            if let Some(info) = probe_media(&input) {
+                                let _ = tx.send(Message::Info(info));
+                            } else {
+                                let _ = tx.send(Message::Status(
+                                    "Failed to read source information.".to_string(),
+                                ));
+                            }
+                        });
                     }
                 }
             });
@@ -113,6 +402,129 @@ impl App for VideoConverterApp {
                     });
             });
 
+            // Ausgabemodus auswählen (Einzeldatei oder Streaming-Paketierung)
+            ui.horizontal(|ui| {
+                ui.label("Output Mode:");
+                egui::ComboBox::from_id_salt("output_mode_salt")
+                    .selected_text(OUTPUT_MODES[self.selected_mode].0)
+                    .show_ui(ui, |ui| {
+                        for (index, (mode_name, _)) in OUTPUT_MODES.iter().enumerate() {
+                            ui.selectable_value(&mut self.selected_mode, index, *mode_name);
+                        }
+                    });
+            });
+
+            // Für Streaming-Modi ein Zielverzeichnis wählen
+            if OUTPUT_MODES[self.selected_mode].1.needs_directory() {
+                ui.horizontal(|ui| {
+                    ui.label("Output Directory:");
+                    ui.text_edit_singleline(&mut self.output_dir);
+                    if ui.button("Browse").clicked() {
+                        if let Some(path) = FileDialog::new().pick_folder() {
+                            self.output_dir = path.to_string_lossy().to_string();
+                        }
+                    }
+                });
+            }
+
+            // Quellinformationen (nur lesend) anzeigen
+            if let Some(info) = &self.media_info {
+                ui.separator();
+                ui.heading("Source Info");
+                ui.label(format!("Duration: {}", info.duration));
+                ui.label(format!("Format: {}", info.format));
+                for (index, track) in info.tracks.iter().enumerate() {
+                    let mut details = format!("Track {} [{}]: {}", index, track.kind, track.codec);
+                    if !track.resolution.is_empty() {
+                        details.push_str(&format!(", {}", track.resolution));
+                    }
+                    if !track.frame_rate.is_empty() {
+                        details.push_str(&format!(", {} fps", track.frame_rate));
+                    }
+                    if !track.sample_rate.is_empty() {
+                        details.push_str(&format!(", {} Hz", track.sample_rate));
+                    }
+                    if !track.language.is_empty() {
+                        details.push_str(&format!(", {}", track.language));
+                    }
+                    ui.label(details);
+                }
+            }
+
+            // Transkodierungs-Einstellungen
+            ui.separator();
+            ui.heading("Transcoding");
+
+            ui.horizontal(|ui| {
+                ui.label("Resolution:");
+                ui.text_edit_singleline(&mut self.encode.width);
+                ui.label("x");
+                ui.text_edit_singleline(&mut self.encode.height);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Frame Rate:");
+                ui.text_edit_singleline(&mut self.encode.frame_rate);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Video Codec:");
+                ui.text_edit_singleline(&mut self.encode.video_codec);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Audio Codec:");
+                ui.text_edit_singleline(&mut self.encode.audio_codec);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Bitrate:");
+                ui.text_edit_singleline(&mut self.encode.bitrate);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("CRF:");
+                ui.text_edit_singleline(&mut self.encode.crf);
+            });
+
+            // Zuschnitt-Einstellungen
+            ui.separator();
+            ui.heading("Trim");
+
+            ui.horizontal(|ui| {
+                ui.label("Start:");
+                ui.text_edit_singleline(&mut self.trim.start);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Duration:");
+                ui.text_edit_singleline(&mut self.trim.duration);
+            });
+
+            ui.checkbox(&mut self.trim.accurate, "Accurate seek (slower, seek after input)");
+
+            // Untertitel-Einstellungen
+            ui.separator();
+            ui.heading("Subtitles");
+
+            ui.horizontal(|ui| {
+                ui.label("Subtitle File:");
+                ui.text_edit_singleline(&mut self.subtitle.path);
+                if ui.button("Browse").clicked() {
+                    if let Some(path) = FileDialog::new()
+                        .add_filter("Subtitles", &["srt", "ass"])
+                        .pick_file()
+                    {
+                        self.subtitle.path = path.to_string_lossy().to_string();
+                    }
+                }
+            });
+
+            ui.checkbox(&mut self.subtitle.auto_sync, "Auto-sync subtitles to speech");
+            if self.subtitle.auto_sync {
+                ui.checkbox(&mut self.subtitle.split_sync, "Allow split offsets (framerate drift / ad breaks)");
+            }
+
             // Metadata input
             ui.separator();
             ui.heading("Metadata");
@@ -132,6 +544,32 @@ impl App for VideoConverterApp {
                 ui.text_edit_multiline(&mut self.metadata_description);
             });
 
+            // Warteschlange der Konvertierungsaufträge
+            ui.separator();
+            ui.heading("Queue");
+
+            ui.horizontal(|ui| {
+                if ui.button("Add to Queue").clicked() && !self.input_file.is_empty() {
+                    // Die Ausgabeerweiterung passend zum gewählten Format korrigieren
+                    let output = normalize_output_extension(
+                        &self.output_file,
+                        SUPPORTED_FORMATS[self.selected_format].1,
+                    );
+                    self.jobs.push(Job {
+                        input: self.input_file.clone(),
+                        output,
+                    });
+                }
+                if ui.button("Clear Queue").clicked() {
+                    self.jobs.clear();
+                }
+                ui.checkbox(&mut self.concat, "Concatenate inputs into single output");
+            });
+
+            for (index, job) in self.jobs.iter().enumerate() {
+                ui.label(format!("{}. {} -> {}", index + 1, job.input, job.output));
+            }
+
             // Fortschrittsanzeige
             ui.horizontal(|ui| {
                 ui.label("Progress:");
@@ -140,157 +578,751 @@ impl App for VideoConverterApp {
 
             // Konvertierung starten
             if ui.button("Convert").clicked() {
-                let input = self.input_file.clone();
-                let mut output = self.output_file.clone();
                 let tx = self.tx.clone();
-                let selected_extension = SUPPORTED_FORMATS[self.selected_format].1.to_string();
-                let metadata_title = self.metadata_title.clone();
-                let metadata_artist = self.metadata_artist.clone();
-                let metadata_description = self.metadata_description.clone();
-
-                // Überprüfe, ob die Ausgabedatei eine Erweiterung hat
-                if Path::new(&output).extension().is_none() {
-                    // Füge die ausgewählte Erweiterung hinzu
-                    output.push_str(&selected_extension);
-                } else {
-                    // Überprüfe, ob die Erweiterung mit dem ausgewählten Format übereinstimmt
-                    let path = Path::new(&output);
-                    if let Some(ext) = path.extension() {
-                        let ext_str = ext.to_string_lossy().to_lowercase();
-                        let selected_ext_str = selected_extension.trim_start_matches('.').to_lowercase();
-                        if ext_str != selected_ext_str {
-                            // Ersetze die falsche Erweiterung durch die richtige
-                            if let Some(stem) = path.file_stem() {
-                                output = stem.to_string_lossy().to_string();
-                                output.push_str(&selected_extension);
-                            }
-                        }
-                    }
-                }
-
-                // Aktualisiere das output_file Feld, falls die Erweiterung hinzugefügt oder ersetzt wurde
-                self.output_file = output.clone();
+                let params = ConversionParams {
+                    encode: self.encode.clone(),
+                    metadata_title: self.metadata_title.clone(),
+                    metadata_artist: self.metadata_artist.clone(),
+                    metadata_description: self.metadata_description.clone(),
+                    output_mode: OUTPUT_MODES[self.selected_mode].1,
+                    output_dir: self.output_dir.clone(),
+                    trim: self.trim.clone(),
+                    subtitle: self.subtitle.clone(),
+                };
+                let jobs = self.jobs.clone();
+                let concat = self.concat;
 
                 // Setze Fortschritt und Status zurück
                 self.progress = 0.0;
                 self.status = "Starting conversion...".to_string();
 
-                // Hintergrund-Thread starten für die Videokonvertierung
+                // Hintergrund-Thread startet und arbeitet die Warteschlange ab
                 thread::spawn(move || {
-                    if input.is_empty() || output.is_empty() {
-                        let _ = tx.send(Message::Status("Please specify both input and output files.".to_string()));
+                    if jobs.is_empty() {
+                        let _ = tx.send(Message::Status("Queue is empty. Add at least one job.".to_string()));
+                        return;
+                    }
+                    if params.output_mode.needs_directory() && params.output_dir.is_empty() {
+                        let _ = tx.send(Message::Status("Please specify an output directory.".to_string()));
                         return;
                     }
 
-                    // Ermitteln der Gesamtdauer des Videos
-                    let duration_output = Command::new("ffmpeg")
-                        .arg("-i")
-                        .arg(&input)
-                        .stderr(Stdio::piped())
-                        .output();
-
-                    let total_duration = match duration_output {
-                        Ok(output) => {
-                            let stderr = String::from_utf8_lossy(&output.stderr);
-                            // Suche nach der Zeile, die die Dauer enthält
-                            if let Some(duration_line) = stderr.lines().find(|line| line.contains("Duration:")) {
-                                // Beispielzeile: " Duration: 00:04:36.10, start: 0.000000, bitrate: 2018 kb/s"
-                                if let Some(start) = duration_line.find("Duration: ") {
-                                    let duration_str = &duration_line[start + 10..start + 19]; // "00:04:36.10"
-                                    if let Ok(total_seconds) = parse_duration(duration_str) {
-                                        total_seconds
-                                    } else {
-                                        let _ = tx.send(Message::Status("Failed to parse video duration.".to_string()));
-                                        return;
-                                    }
-                                } else {
-                                    let _ = tx.send(Message::Status("Failed to find duration information.".to_string()));
-                                    return;
-                                }
-                            } else {
-                                let _ = tx.send(Message::Status("Failed to retrieve video duration.".to_string()));
-                                return;
-                            }
-                        }
-                        Err(e) => {
-                            let _ = tx.send(Message::Status(format!("Failed to execute ffmpeg for duration: {}", e)));
-                            return;
+                    if concat {
+                        // Alle Eingaben zu einer einzigen Ausgabe verketten
+                        let inputs: Vec<String> = jobs.iter().map(|job| job.input.clone()).collect();
+                        run_job(&inputs, &jobs[0].output, &params, &tx, 0, 1);
+                    } else {
+                        // Jobs nacheinander abarbeiten; ein fehlgeschlagener Job
+                        // bricht die übrige Warteschlange nicht ab.
+                        let total = jobs.len();
+                        for (index, job) in jobs.iter().enumerate() {
+                            run_job(std::slice::from_ref(&job.input), &job.output, &params, &tx, index, total);
                         }
-                    };
+                    }
 
-                    // Starte die eigentliche Konvertierung und überwache den Fortschritt
-                    let mut ffmpeg_command = Command::new("ffmpeg");
-                    ffmpeg_command
-                        .arg("-i")
-                        .arg(&input)
-                        .arg(&output);
+                    let _ = tx.send(Message::Progress(100.0));
+                    let _ = tx.send(Message::Status("Queue finished.".to_string()));
+                });
+            }
 
-                    // Füge die Metadaten hinzu, falls sie vorhanden sind
-                    if !metadata_title.is_empty() {
-                        ffmpeg_command = ffmpeg_command.arg("-metadata").arg(format!("title={}", metadata_title));
-                    }
-                    if !metadata_artist.is_empty() {
-                        ffmpeg_command = ffmpeg_command.arg("-metadata").arg(format!("artist={}", metadata_artist));
-                    }
-                    if !metadata_description.is_empty() {
-                        ffmpeg_command = ffmpeg_command.arg("-metadata").arg(format!("description={}", metadata_description));
-                    }
+            // Status und Fortschritt anzeigen
+            ui.separator();
+            ui.label("Status:");
+            ui.label(&self.status);
+        });
+    }
+}
+
+// Korrigiert bzw. ergänzt die Erweiterung der Ausgabedatei passend zum
+// ausgewählten Format.
+fn normalize_output_extension(output: &str, extension: &str) -> String {
+    let mut result = output.to_string();
+    if Path::new(&result).extension().is_none() {
+        // Füge die ausgewählte Erweiterung hinzu
+        result.push_str(extension);
+    } else {
+        // Überprüfe, ob die Erweiterung mit dem ausgewählten Format übereinstimmt
+        let path = Path::new(&result);
+        if let Some(ext) = path.extension() {
+            let ext_str = ext.to_string_lossy().to_lowercase();
+            let selected_ext_str = extension.trim_start_matches('.').to_lowercase();
+            if ext_str != selected_ext_str {
+                // Ersetze die falsche Erweiterung durch die richtige
+                result = path
+                    .with_extension(extension.trim_start_matches('.'))
+                    .to_string_lossy()
+                    .to_string();
+            }
+        }
+    }
+    result
+}
+
+// Liest die Gesamtdauer (in Sekunden) einer Datei aus der ffmpeg-Ausgabe.
+fn probe_duration(input: &str) -> Option<f32> {
+    let output = Command::new("ffmpeg")
+        .arg("-i")
+        .arg(input)
+        .stderr(Stdio::piped())
+        .output()
+        .ok()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // Beispielzeile: " Duration: 00:04:36.10, start: 0.000000, bitrate: 2018 kb/s"
+    let duration_line = stderr.lines().find(|line| line.contains("Duration:"))?;
+    let start = duration_line.find("Duration: ")?;
+    let duration_str = &duration_line[start + 10..start + 19]; // "00:04:36." (ohne Hundertstel)
+    parse_duration(duration_str).ok()
+}
 
-                    ffmpeg_command
-                        .stderr(Stdio::piped())
-                        .stdout(Stdio::null());
+// Führt einen einzelnen Job der Warteschlange aus und meldet den Fortschritt
+// skaliert auf den Gesamtfortschritt der Warteschlange. Bei mehreren Eingaben
+// werden diese über den ffmpeg-Concat-Demuxer verkettet.
+fn run_job(
+    inputs: &[String],
+    output: &str,
+    params: &ConversionParams,
+    tx: &Sender<Message>,
+    job_index: usize,
+    total_jobs: usize,
+) -> bool {
+    let label = format!("[{}/{}]", job_index + 1, total_jobs);
 
-                    let mut ffmpeg_process = match ffmpeg_command.spawn() {
-                        Ok(process) => process,
-                        Err(e) => {
-                            let _ = tx.send(Message::Status(format!("Failed to start ffmpeg: {}", e)));
-                            return;
-                        }
+    // Gesamtdauer ermitteln (bei Verkettung die Summe der Einzeldauern).
+    // Die Dauer der Referenzdatei (erste Eingabe) wird getrennt gemerkt, da die
+    // Sprach-Erkennung nur auf ihr läuft.
+    let mut total_duration = 0.0;
+    let mut reference_duration = 0.0;
+    for (index, input) in inputs.iter().enumerate() {
+        match probe_duration(input) {
+            Some(seconds) => {
+                total_duration += seconds;
+                if index == 0 {
+                    reference_duration = seconds;
+                }
+            }
+            None => {
+                let _ = tx.send(Message::Status(format!("{} Failed to retrieve video duration.", label)));
+                return false;
+            }
+        }
+    }
+
+    let _ = tx.send(Message::Status(format!("{} Converting...", label)));
+
+    // Die für den Fortschritt maßgebliche Dauer entspricht dem Zuschnitt.
+    let effective_duration = params.trim.effective_duration(total_duration);
+
+    // Untertiteldatei vorbereiten und bei Bedarf automatisch neu synchronisieren.
+    let mut subtitle_temp = None;
+    let subtitle_input = if params.subtitle.path.is_empty() {
+        None
+    } else if !supports_soft_subtitles(output) {
+        // Untertitel in einen Container zu muxen, der sie nicht unterstützt,
+        // würde ffmpeg abbrechen lassen – daher überspringen und melden.
+        let _ = tx.send(Message::Status(format!(
+            "{} Subtitles skipped: output container does not support soft subtitles.",
+            label
+        )));
+        None
+    } else if params.subtitle.auto_sync {
+        match resync_subtitle(&params.subtitle, &inputs[0], reference_duration, job_index) {
+            Some(path) => {
+                subtitle_temp = Some(path.clone());
+                Some(path)
+            }
+            None => {
+                // Synchronisierung nicht möglich: Originaldatei unverändert einbetten
+                let _ = tx.send(Message::Status(format!("{} Subtitle auto-sync skipped.", label)));
+                Some(params.subtitle.path.clone())
+            }
+        }
+    } else {
+        Some(params.subtitle.path.clone())
+    };
+
+    // Starte die eigentliche Konvertierung und überwache den Fortschritt
+    let mut ffmpeg_command = Command::new("ffmpeg");
+
+    // Schnelles Suchen: -ss muss vor -i stehen
+    params.trim.apply_pre_input(&mut ffmpeg_command);
+
+    let mut concat_list = None;
+    if inputs.len() > 1 {
+        // Temporäre Listendatei für den Concat-Demuxer erzeugen
+        let list_path = std::env::temp_dir().join(format!("concat_{}.txt", job_index));
+        let list: String = inputs.iter().map(|input| format!("file '{}'\n", input)).collect();
+        if std::fs::write(&list_path, list).is_err() {
+            let _ = tx.send(Message::Status(format!("{} Failed to write concat list.", label)));
+            return false;
+        }
+        ffmpeg_command
+            .arg("-f")
+            .arg("concat")
+            .arg("-safe")
+            .arg("0")
+            .arg("-i")
+            .arg(&list_path);
+        concat_list = Some(list_path);
+    } else {
+        ffmpeg_command.arg("-i").arg(&inputs[0]);
+    }
+
+    // Externe Untertiteldatei als zusätzliche Eingabe einhängen
+    if let Some(subtitle) = &subtitle_input {
+        // Beim schnellen Suchen muss der Versatz auch auf die Untertitel-Eingabe
+        // angewendet werden, sonst läuft die Spur um `start` Sekunden versetzt.
+        // Beim genauen Suchen wirkt der Versatz als Ausgabeoption ohnehin auf
+        // alle Spuren.
+        params.trim.apply_pre_input(&mut ffmpeg_command);
+        ffmpeg_command.arg("-i").arg(subtitle);
+    }
+
+    // Genaues Suchen und Dauerbegrenzung stehen nach -i
+    params.trim.apply_post_input(&mut ffmpeg_command);
+
+    // Ziel-Auflösung, Bildrate, Codecs und Bitrate/CRF anwenden
+    params.encode.apply(&mut ffmpeg_command);
+
+    // Untertitel als weiche Spur muxen; der Codec richtet sich nach dem Container.
+    // Alle Streams der Quelle (-map 0) und die externe Untertitelspur (-map 1)
+    // werden explizit abgebildet, damit die angehängten Untertitel zuverlässig
+    // übernommen werden, auch wenn die Quelle bereits eigene Spuren enthält.
+    if subtitle_input.is_some() {
+        ffmpeg_command
+            .arg("-map")
+            .arg("0")
+            .arg("-map")
+            .arg("1")
+            .arg("-c:s")
+            .arg(subtitle_codec(output));
+    }
+
+    // Füge die Metadaten hinzu, falls sie vorhanden sind
+    if !params.metadata_title.is_empty() {
+        ffmpeg_command.arg("-metadata").arg(format!("title={}", params.metadata_title));
+    }
+    if !params.metadata_artist.is_empty() {
+        ffmpeg_command.arg("-metadata").arg(format!("artist={}", params.metadata_artist));
+    }
+    if !params.metadata_description.is_empty() {
+        ffmpeg_command.arg("-metadata").arg(format!("description={}", params.metadata_description));
+    }
+
+    // Ausgabeargumente je nach Modus anhängen; der Pfad des erzeugten
+    // Manifests bzw. der Datei wird gemeldet.
+    let manifest = params.output_mode.apply(&mut ffmpeg_command, output, &params.output_dir);
+
+    ffmpeg_command
+        .stderr(Stdio::piped())
+        .stdout(Stdio::null());
+
+    let mut ffmpeg_process = match ffmpeg_command.spawn() {
+        Ok(process) => process,
+        Err(e) => {
+            let _ = tx.send(Message::Status(format!("{} Failed to start ffmpeg: {}", label, e)));
+            return false;
+        }
+    };
+
+    let stderr = ffmpeg_process.stderr.take().expect("Failed to capture stderr");
+    let reader = BufReader::new(stderr);
+
+    for line in reader.lines() {
+        if let Ok(line) = line {
+            // Suche nach "time=HH:MM:SS.xx"
+            if let Some(time_pos) = line.find("time=") {
+                let time_str = &line[time_pos + 5..];
+                // Extrahiere die Zeit bis zum nächsten Komma oder Leerzeichen
+                let end_pos = time_str.find(|c: char| c == ' ' || c == ',').unwrap_or(time_str.len());
+                let time = &time_str[..end_pos];
+                if let Ok(current_seconds) = parse_duration(time) {
+                    // Fortschritt dieses Jobs auf die gesamte Warteschlange skalieren;
+                    // 100% entsprechen der zugeschnittenen Dauer.
+                    let job_fraction = if effective_duration > 0.0 {
+                        current_seconds / effective_duration
+                    } else {
+                        0.0
                     };
+                    let overall = ((job_index as f32 + job_fraction) / total_jobs as f32) * 100.0;
+                    let overall = if overall > 100.0 { 100.0 } else { overall };
+                    let _ = tx.send(Message::Progress(overall));
+                }
+            }
+        }
+    }
 
-                    let stderr = ffmpeg_process.stderr.take().expect("Failed to capture stderr");
-                    let reader = BufReader::new(stderr);
-
-                    for line in reader.lines() {
-                        if let Ok(line) = line {
-                            // Suche nach "time=HH:MM:SS.xx"
-                            if let Some(time_pos) = line.find("time=") {
-                                let time_str = &line[time_pos + 5..];
-                                // Extrahiere die Zeit bis zum nächsten Komma oder Leerzeichen
-                                let end_pos = time_str.find(|c: char| c == ' ' || c == ',').unwrap_or(time_str.len());
-                                let time = &time_str[..end_pos];
-                                if let Ok(current_seconds) = parse_duration(time) {
-                                    let progress = (current_seconds / total_duration) * 100.0;
-                                    let progress = if progress > 100.0 { 100.0 } else { progress };
-                                    let _ = tx.send(Message::Progress(progress));
-                                }
-                            }
-                        }
-                    }
+    // Warte auf das Ende des ffmpeg-Prozesses
+    let success = match ffmpeg_process.wait() {
+        Ok(status) => {
+            if status.success() {
+                let _ = tx.send(Message::Status(format!("{} Conversion successful. Output: {}", label, manifest)));
+                true
+            } else {
+                let _ = tx.send(Message::Status(format!("{} Conversion failed.", label)));
+                false
+            }
+        }
+        Err(e) => {
+            let _ = tx.send(Message::Status(format!("{} Failed to wait on ffmpeg: {}", label, e)));
+            false
+        }
+    };
 
-                    // Warte auf das Ende des ffmpeg-Prozesses
-                    match ffmpeg_process.wait() {
-                        Ok(status) => {
-                            if status.success() {
-                                let _ = tx.send(Message::Status("Conversion successful.".to_string()));
-                                let _ = tx.send(Message::Progress(100.0));
-                            } else {
-                                let _ = tx.send(Message::Status("Conversion failed.".to_string()));
+    // Temporäre Concat-Liste wieder entfernen
+    if let Some(list_path) = concat_list {
+        let _ = std::fs::remove_file(list_path);
+    }
+    // Temporäre, neu synchronisierte Untertiteldatei wieder entfernen
+    if let Some(subtitle_path) = subtitle_temp {
+        let _ = std::fs::remove_file(subtitle_path);
+    }
+
+    success
+}
+
+// Prüft, ob der Zielcontainer weiche Untertitelspuren aufnehmen kann. AVI, WMV
+// und FLV können das nicht; ffmpeg würde den gesamten Job abbrechen.
+fn supports_soft_subtitles(output: &str) -> bool {
+    let lower = output.to_lowercase();
+    lower.ends_with(".mkv")
+        || lower.ends_with(".mp4")
+        || lower.ends_with(".mov")
+        || lower.ends_with(".m4v")
+}
+
+// Wählt den passenden Untertitel-Codec je nach Zielcontainer: MP4-Familie
+// verlangt `mov_text`, MKV übernimmt die Spur unverändert.
+fn subtitle_codec(output: &str) -> &'static str {
+    let lower = output.to_lowercase();
+    if lower.ends_with(".mp4") || lower.ends_with(".mov") || lower.ends_with(".m4v") {
+        "mov_text"
+    } else {
+        "copy"
+    }
+}
+
+// Synchronisiert eine externe Untertiteldatei auf die Sprache des Videos und
+// schreibt das Ergebnis in eine temporäre Datei, deren Pfad zurückgegeben wird.
+// Nur `.srt`-Dateien werden umgerechnet; andere Formate werden unverändert
+// gelassen (kein Ergebnis). `reference_duration` ist die Dauer der
+// Referenzdatei: Bei verketteten Jobs erfolgt die Sprach-Erkennung nur auf der
+// ersten Eingabe, sodass die Angleichung auf deren Zeitachse beschränkt ist.
+fn resync_subtitle(
+    settings: &SubtitleSettings,
+    reference: &str,
+    reference_duration: f32,
+    job_index: usize,
+) -> Option<String> {
+    if !settings.path.to_lowercase().ends_with(".srt") {
+        return None;
+    }
+
+    let content = std::fs::read_to_string(&settings.path).ok()?;
+    let cues = parse_srt(&content);
+    if cues.is_empty() {
+        return None;
+    }
+
+    let speech = detect_speech_intervals(reference, reference_duration);
+    if speech.is_empty() {
+        return None;
+    }
+
+    let shifted = resync_cues(&cues, &speech, settings.split_sync);
+    let output_path = std::env::temp_dir().join(format!("resynced_{}.srt", job_index));
+    std::fs::write(&output_path, format_srt(&shifted)).ok()?;
+    Some(output_path.to_string_lossy().to_string())
+}
+
+// Führt einen `silencedetect`-Durchlauf aus und leitet daraus die
+// Sprach-Intervalle (das Komplement der Stille) innerhalb von
+// [0, total_duration] ab.
+fn detect_speech_intervals(input: &str, total_duration: f32) -> Vec<(f32, f32)> {
+    let output = Command::new("ffmpeg")
+        .arg("-i")
+        .arg(input)
+        .arg("-af")
+        .arg("silencedetect=noise=-30dB:d=0.5")
+        .arg("-f")
+        .arg("null")
+        .arg("-")
+        .stderr(Stdio::piped())
+        .output();
+
+    let output = match output {
+        Ok(output) => output,
+        Err(_) => return Vec::new(),
+    };
+
+    let text = String::from_utf8_lossy(&output.stderr);
+    let mut silences: Vec<(f32, f32)> = Vec::new();
+    let mut pending_start: Option<f32> = None;
+
+    for line in text.lines() {
+        if let Some(pos) = line.find("silence_start:") {
+            pending_start = line[pos + "silence_start:".len()..]
+                .split_whitespace()
+                .next()
+                .and_then(|value| value.parse::<f32>().ok());
+        } else if let Some(pos) = line.find("silence_end:") {
+            if let Some(end) = line[pos + "silence_end:".len()..]
+                .split_whitespace()
+                .next()
+                .and_then(|value| value.trim_end_matches('|').parse::<f32>().ok())
+            {
+                if let Some(start) = pending_start.take() {
+                    silences.push((start, end));
+                }
+            }
+        }
+    }
+
+    // Das Komplement der Stille ergibt die Sprach-Intervalle.
+    let mut speech = Vec::new();
+    let mut cursor = 0.0;
+    for (start, end) in silences {
+        if start > cursor {
+            speech.push((cursor, start));
+        }
+        cursor = cursor.max(end);
+    }
+    if total_duration > cursor {
+        speech.push((cursor, total_duration));
+    }
+    speech
+}
+
+// Plausibler Maximalbetrag eines Offsets (Sekunden). Kandidaten außerhalb
+// dieses Fensters werden verworfen, was die Kandidatenmenge begrenzt.
+const MAX_SHIFT: f32 = 30.0;
+// Obergrenze der Blocklänge im Split-Modus. Ohne diese Grenze wäre die DP
+// quadratisch in der Cue-Zahl und würde bei langen Untertiteln unpraktikabel.
+const MAX_BLOCK_SPAN: usize = 32;
+
+// Kumulative Sprachabdeckung für schnelle Überlappungsabfragen. Die
+// Sprach-Intervalle sind disjunkt und nach Startzeit sortiert; `prefix[k]` hält
+// die aufsummierte Sprachdauer der ersten `k` Intervalle.
+struct SpeechCoverage {
+    intervals: Vec<(f32, f32)>,
+    prefix: Vec<f32>,
+}
+
+impl SpeechCoverage {
+    fn new(intervals: &[(f32, f32)]) -> Self {
+        let mut prefix = Vec::with_capacity(intervals.len() + 1);
+        prefix.push(0.0);
+        let mut acc = 0.0;
+        for &(start, end) in intervals {
+            acc += (end - start).max(0.0);
+            prefix.push(acc);
+        }
+        Self { intervals: intervals.to_vec(), prefix }
+    }
+
+    // Gesamte Sprachdauer im Intervall [0, t].
+    fn coverage_until(&self, t: f32) -> f32 {
+        // Index des ersten Intervalls mit Start >= t.
+        let idx = self.intervals.partition_point(|&(start, _)| start < t);
+        let mut total = self.prefix[idx];
+        // Das letzte Intervall vor `t` kann über `t` hinausragen.
+        if idx > 0 {
+            let (_, end) = self.intervals[idx - 1];
+            if end > t {
+                total -= end - t;
+            }
+        }
+        total.max(0.0)
+    }
+
+    // Überlappung des Intervalls [start, end] mit der Sprache.
+    fn overlap(&self, start: f32, end: f32) -> f32 {
+        (self.coverage_until(end) - self.coverage_until(start)).max(0.0)
+    }
+}
+
+// Verschiebt alle Cues so, dass die Überlappung mit den Sprach-Intervallen
+// maximiert wird – entweder mit einem globalen Offset oder, im Split-Modus,
+// mit je einem Offset pro zusammenhängendem Block.
+fn resync_cues(cues: &[Cue], speech: &[(f32, f32)], split: bool) -> Vec<Cue> {
+    let coverage = SpeechCoverage::new(speech);
+    if split {
+        let offsets = split_offsets(cues, &coverage, 5.0);
+        cues.iter()
+            .zip(offsets)
+            .map(|(cue, offset)| shift_cue(cue, offset))
+            .collect()
+    } else {
+        let (offset, _) = best_global_offset(cues, &coverage);
+        cues.iter().map(|cue| shift_cue(cue, offset)).collect()
+    }
+}
+
+// Verschiebt einen Cue um `offset` Sekunden, ohne negativ zu werden.
+fn shift_cue(cue: &Cue, offset: f32) -> Cue {
+    let start = (cue.start + offset).max(0.0);
+    let end = (cue.end + offset).max(start);
+    Cue { start, end, text: cue.text.clone() }
+}
+
+// Summiert die zeitliche Überlappung aller um `offset` verschobenen Cues mit
+// den Sprach-Intervallen. Jede Cue-Überlappung wird über die kumulative
+// Abdeckung in O(log s) bestimmt.
+fn overlap_score(cues: &[Cue], coverage: &SpeechCoverage, offset: f32) -> f32 {
+    cues.iter()
+        .map(|cue| coverage.overlap(cue.start + offset, cue.end + offset))
+        .sum()
+}
+
+// Bestimmt den globalen Offset mit maximaler Überlappung. Die Überlappung ist
+// stückweise linear in δ; ihre Maxima liegen an den Grenzen, an denen sich eine
+// Cue- und eine Sprach-Kante treffen. Es werden genau diese Kandidaten sowie
+// δ = 0 ausgewertet, beschränkt auf [-MAX_SHIFT, MAX_SHIFT] und einmalig
+// dedupliziert.
+fn best_global_offset(cues: &[Cue], coverage: &SpeechCoverage) -> (f32, f32) {
+    if cues.is_empty() || coverage.intervals.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let mut candidates = vec![0.0f32];
+    for cue in cues {
+        for &(speech_start, speech_end) in &coverage.intervals {
+            for delta in [
+                speech_start - cue.start,
+                speech_start - cue.end,
+                speech_end - cue.start,
+                speech_end - cue.end,
+            ] {
+                if delta.abs() <= MAX_SHIFT {
+                    candidates.push(delta);
+                }
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    candidates.dedup();
+
+    let mut best_offset = 0.0;
+    let mut best_score = f32::MIN;
+    for &offset in &candidates {
+        let score = overlap_score(cues, coverage, offset);
+        if score > best_score {
+            best_score = score;
+            best_offset = offset;
+        }
+    }
+    (best_offset, best_score)
+}
+
+// Teilt die Cue-Folge in zusammenhängende Blöcke mit je eigenem Offset auf.
+// Gelöst wird per dynamischer Programmierung über die Cues, die
+// `-overlap_score + split_penalty * num_splits` minimiert. Die Blocklänge ist
+// auf `MAX_BLOCK_SPAN` begrenzt, damit die Laufzeit linear in der Cue-Zahl
+// bleibt. Bis zu dieser Länge entstehen zusätzliche Splits nur, wenn sie die
+// Ausrichtung deutlich verbessern; bei mehr als `MAX_BLOCK_SPAN` Cues entstehen
+// durch die Begrenzung auch Splits, die die Ausrichtung nicht verbessern.
+// Liefert den zugewiesenen Offset je Cue.
+fn split_offsets(cues: &[Cue], coverage: &SpeechCoverage, split_penalty: f32) -> Vec<f32> {
+    let n = cues.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    // dp[j] = minimale Kosten, die ersten j Cues abzudecken.
+    let mut dp = vec![f32::INFINITY; n + 1];
+    let mut back = vec![0usize; n + 1];
+    let mut block_offset = vec![0.0f32; n + 1];
+    dp[0] = 0.0;
+
+    for j in 1..=n {
+        // Nur Blöcke bis zur maximalen Spanne betrachten.
+        let first = j.saturating_sub(MAX_BLOCK_SPAN);
+        for i in first..j {
+            if dp[i].is_infinite() {
+                continue;
+            }
+            let (offset, overlap) = best_global_offset(&cues[i..j], coverage);
+            let penalty = if i > 0 { split_penalty } else { 0.0 };
+            let cost = dp[i] - overlap + penalty;
+            if cost < dp[j] {
+                dp[j] = cost;
+                back[j] = i;
+                block_offset[j] = offset;
+            }
+        }
+    }
+
+    // Rückwärts jeden Cue dem Offset seines Blocks zuordnen.
+    let mut offsets = vec![0.0f32; n];
+    let mut j = n;
+    while j > 0 {
+        let i = back[j];
+        for offset in offsets.iter_mut().take(j).skip(i) {
+            *offset = block_offset[j];
+        }
+        j = i;
+    }
+    offsets
+}
+
+// Zerlegt SRT-Inhalt in einzelne Cues.
+fn parse_srt(content: &str) -> Vec<Cue> {
+    let normalized = content.replace("\r\n", "\n");
+    let mut cues = Vec::new();
+
+    for block in normalized.split("\n\n") {
+        let mut lines = block.lines().filter(|line| !line.trim().is_empty());
+        // Die erste Zeile ist der Index, die zweite das Zeitintervall.
+        if lines.next().is_none() {
+            continue;
+        }
+        let timing = match lines.next() {
+            Some(line) => line,
+            None => continue,
+        };
+        let (start, end) = match parse_srt_timing(timing) {
+            Some(interval) => interval,
+            None => continue,
+        };
+        let text = lines.collect::<Vec<_>>().join("\n");
+        cues.push(Cue { start, end, text });
+    }
+
+    cues
+}
+
+// Zerlegt eine Zeitzeile "HH:MM:SS,mmm --> HH:MM:SS,mmm" in Start und Ende.
+fn parse_srt_timing(line: &str) -> Option<(f32, f32)> {
+    let (start, end) = line.split_once(" --> ")?;
+    Some((parse_timestamp(start.trim())?, parse_timestamp(end.trim())?))
+}
+
+// Wandelt einen SRT-Zeitstempel "HH:MM:SS,mmm" in Sekunden um.
+fn parse_timestamp(timestamp: &str) -> Option<f32> {
+    parse_duration(&timestamp.replace(',', ".")).ok()
+}
+
+// Setzt eine Liste von Cues wieder zu SRT-Inhalt zusammen.
+fn format_srt(cues: &[Cue]) -> String {
+    let mut output = String::new();
+    for (index, cue) in cues.iter().enumerate() {
+        output.push_str(&format!("{}\n", index + 1));
+        output.push_str(&format!(
+            "{} --> {}\n",
+            format_timestamp(cue.start),
+            format_timestamp(cue.end)
+        ));
+        output.push_str(&cue.text);
+        output.push_str("\n\n");
+    }
+    output
+}
+
+// Formatiert Sekunden als SRT-Zeitstempel "HH:MM:SS,mmm".
+fn format_timestamp(seconds: f32) -> String {
+    let total_ms = (seconds * 1000.0).round() as i64;
+    let ms = total_ms % 1000;
+    let total_seconds = total_ms / 1000;
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        total_seconds / 3600,
+        (total_seconds / 60) % 60,
+        total_seconds % 60,
+        ms
+    )
+}
+
+// Ermittelt die Container- und Spur-Informationen einer Datei über ffprobe.
+// Es wird die strukturierte `default`-Ausgabe zeilenweise ausgewertet,
+// analog zum bestehenden Scraping der ffmpeg-Ausgabe.
+fn probe_media(path: &str) -> Option<MediaInfo> {
+    let output = Command::new("ffprobe")
+        .args(["-v", "quiet", "-show_streams", "-show_format", "-of", "default"])
+        .arg(path)
+        .stdout(Stdio::piped())
+        .output()
+        .ok()?;
+
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut info = MediaInfo::default();
+    let mut current: Option<TrackInfo> = None;
+    let mut in_format = false;
+
+    for line in text.lines() {
+        match line.trim() {
+            "[STREAM]" => current = Some(TrackInfo::default()),
+            "[/STREAM]" => {
+                if let Some(mut track) = current.take() {
+                    // Auflösung einmalig aus Breite und Höhe zusammensetzen,
+                    // unabhängig von der Reihenfolge der ffprobe-Zeilen.
+                    if !track.width.is_empty() || !track.height.is_empty() {
+                        track.resolution = format!("{}x{}", track.width, track.height);
+                    }
+                    // Die Bildrate ist nur bei Videospuren aussagekräftig.
+                    if track.kind != "video" {
+                        track.frame_rate.clear();
+                    }
+                    info.tracks.push(track);
+                }
+            }
+            "[FORMAT]" => in_format = true,
+            "[/FORMAT]" => in_format = false,
+            entry => {
+                let (key, value) = match entry.split_once('=') {
+                    Some(pair) => pair,
+                    None => continue,
+                };
+                if let Some(track) = current.as_mut() {
+                    match key {
+                        "codec_type" => track.kind = value.to_string(),
+                        "codec_name" => track.codec = value.to_string(),
+                        "width" => track.width = value.to_string(),
+                        "height" => track.height = value.to_string(),
+                        "r_frame_rate" => track.frame_rate = format_frame_rate(value),
+                        "sample_rate" => track.sample_rate = value.to_string(),
+                        "TAG:language" => track.language = value.to_string(),
+                        _ => {}
+                    }
+                } else if in_format {
+                    match key {
+                        "duration" => {
+                            if let Ok(seconds) = value.parse::<f32>() {
+                                info.duration = format_seconds(seconds);
                             }
                         }
-                        Err(e) => {
-                            let _ = tx.send(Message::Status(format!("Failed to wait on ffmpeg: {}", e)));
+                        "format_long_name" if info.format.is_empty() => {
+                            info.format = value.to_string();
                         }
+                        "TAG:major_brand" => info.format = value.to_string(),
+                        _ => {}
                     }
-                });
+                }
             }
+        }
+    }
 
-            // Status und Fortschritt anzeigen
-            ui.separator();
-            ui.label("Status:");
-            ui.label(&self.status);
-        });
+    Some(info)
+}
+
+// Wandelt eine ffprobe-Frameratenangabe wie "30000/1001" in eine gerundete
+// Bilder-pro-Sekunde-Zeichenkette um. Bei einem Nenner von 0 (ffprobe meldet
+// `0/0` für Audio- und Untertitelspuren) wird eine leere Zeichenkette geliefert.
+fn format_frame_rate(raw: &str) -> String {
+    if let Some((num, den)) = raw.split_once('/') {
+        if let (Ok(num), Ok(den)) = (num.parse::<f32>(), den.parse::<f32>()) {
+            if den != 0.0 {
+                return format!("{:.2}", num / den);
+            }
+            return String::new();
+        }
     }
+    raw.to_string()
+}
+
+// Formatiert Sekunden als "HH:MM:SS".
+fn format_seconds(total: f32) -> String {
+    let total = total as u64;
+    format!("{:02}:{:02}:{:02}", total / 3600, (total % 3600) / 60, total % 60)
 }
 
 // Funktion zur Umwandlung von "HH:MM:SS.xx" in Sekunden
@@ -307,6 +1339,82 @@ fn parse_duration(duration: &str) -> Result<f32, ()> {
     Ok(hours * 3600.0 + minutes * 60.0 + seconds)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-3, "expected {}, got {}", b, a);
+    }
+
+    #[test]
+    fn timestamp_round_trip() {
+        approx(parse_timestamp("01:01:01,500").unwrap(), 3661.5);
+        assert_eq!(format_timestamp(3661.5), "01:01:01,500");
+        // Null wird vollständig mit führenden Nullen formatiert
+        assert_eq!(format_timestamp(0.0), "00:00:00,000");
+    }
+
+    #[test]
+    fn srt_parse_and_format_round_trip() {
+        let input = "1\n00:00:01,000 --> 00:00:02,000\nHello\n\n\
+                     2\n00:00:03,500 --> 00:00:05,000\nSecond line\n\n";
+        let cues = parse_srt(input);
+        assert_eq!(cues.len(), 2);
+        approx(cues[0].start, 1.0);
+        approx(cues[0].end, 2.0);
+        assert_eq!(cues[0].text, "Hello");
+        approx(cues[1].start, 3.5);
+        assert_eq!(cues[1].text, "Second line");
+
+        // Erneutes Parsen der formatierten Ausgabe liefert dieselben Cues.
+        let reparsed = parse_srt(&format_srt(&cues));
+        assert_eq!(reparsed.len(), cues.len());
+        for (a, b) in reparsed.iter().zip(&cues) {
+            approx(a.start, b.start);
+            approx(a.end, b.end);
+            assert_eq!(a.text, b.text);
+        }
+    }
+
+    #[test]
+    fn srt_timing_rejects_malformed() {
+        assert!(parse_srt_timing("not a timing line").is_none());
+    }
+
+    #[test]
+    fn speech_coverage_overlap() {
+        let coverage = SpeechCoverage::new(&[(0.0, 10.0), (20.0, 30.0)]);
+        approx(coverage.overlap(0.0, 10.0), 10.0);
+        approx(coverage.overlap(5.0, 25.0), 10.0);
+        // Vollständig in der Stille liegendes Intervall
+        approx(coverage.overlap(12.0, 18.0), 0.0);
+    }
+
+    #[test]
+    fn global_offset_aligns_single_cue() {
+        let cues = vec![Cue { start: 1.0, end: 2.0, text: "x".to_string() }];
+        let coverage = SpeechCoverage::new(&[(5.0, 6.0)]);
+        let (offset, score) = best_global_offset(&cues, &coverage);
+        approx(offset, 4.0);
+        approx(score, 1.0);
+    }
+
+    #[test]
+    fn split_offsets_assigns_per_block() {
+        let cues = vec![
+            Cue { start: 0.0, end: 1.0, text: "a".to_string() },
+            Cue { start: 100.0, end: 101.0, text: "b".to_string() },
+        ];
+        let coverage = SpeechCoverage::new(&[(5.0, 6.0), (110.0, 111.0)]);
+        // Niedrige Strafe erlaubt den Split, da er die Ausrichtung deutlich verbessert.
+        let offsets = split_offsets(&cues, &coverage, 0.5);
+        assert_eq!(offsets.len(), 2);
+        approx(offsets[0], 5.0);
+        approx(offsets[1], 10.0);
+    }
+}
+
 fn main() {
     let native_options = eframe::NativeOptions::default();
     eframe::run_native(